@@ -0,0 +1,279 @@
+/*
+ * SEIR.c - This function stores all of the differential functions that are
+ *          used with the functional multiprocessing method to produce our SEIR model.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <math.h>
+#include "SEIR.h"
+
+// These variables are initialized and assigned their values in the SEIR.cpp
+// program, but we still need them to be within the scope of the SEIR.c file.
+// Thus, they are declared here, but they are not initialized.
+
+// Year and month for the simulation to keep track of.
+int NowYear = 0;			// [0, Num_Years]
+int NumYears = NUM_YEARS;
+int NowMonth = 0;			// [0, 11]
+
+// Starting number of susceptible people, exposed people, infected people, and
+// recovered people.
+long CurrentInfected = 10;
+long CurrentSusceptible = 175000;
+long CurrentExposed = 0;
+long CurrentRecovered = 0;
+
+// Transfer rates for the SEIR model.
+// Rate of infection for measles. The newly infected are exposed first.
+double InfectionRate = 0.4;
+// Rate at which the exposed become infectious, i.e., one over the length of
+// the incubation period in months.
+double IncubationRate = 0.5;
+// Rate of recovery for measles.
+double RecoveryRate = 0.04;
+
+// File to write the simulation to as CSV data. This stays NULL unless the
+// user provides one with the -o flag.
+FILE* CsvFile = NULL;
+
+
+/* RoundedFlow: This function returns the number of individuals who leave a group
+ *              of the given size this month at the given rate, rounded to the
+ *              nearest person. The product is rounded as a double, which can
+ *              round a very large group up past LONG_MAX, so the flow is clamped
+ *              to the size of the group before it is converted back to a long.
+ */
+long RoundedFlow(long source, double rate) {
+    double flow = round(source * rate);
+
+    if (flow >= (double)source)
+        return source;
+
+    return flow;
+}
+
+
+/* BasicReproductionNumber: This function returns R0 = InfectionRate / RecoveryRate.
+ *                          This is the frequency-dependent definition for an SEIR
+ *                          model with new infections of InfectionRate * S * I/N.
+ *                          The IncubationRate only matters when it is 0: without
+ *                          any deaths, everyone who is exposed becomes infectious
+ *                          eventually, so the incubation period delays the
+ *                          infections rather than preventing them. If no one
+ *                          ever becomes infectious, R0 is 0, and without
+ *                          recovery it is infinite.
+ * IMPORTANT: Just like in the SIR model, this model's new infections are
+ *            CurrentSusceptible * InfectionRate, which doesn't depend on the
+ *            number of infected, so the outbreak doesn't follow this R0. It is
+ *            only reported to compare the rates against the standard model.
+ */
+double BasicReproductionNumber() {
+    if (InfectionRate == 0.0 || IncubationRate == 0.0)
+        return 0.0;
+
+    if (RecoveryRate == 0.0)
+        return INFINITY;
+
+    return InfectionRate / RecoveryRate;
+}
+
+
+/* Susceptible: This function is executed by a thread in parallel with the
+ *              Exposed(), Infected(), Recovered(), and Watcher() functions.
+ *              It serves to calculate the next value of the Susceptible
+ *              population, as the Susceptible catch the disease and become
+ *              Exposed.
+ */
+void Susceptible() {
+    long nextSusceptible;
+
+    while( NowYear < NumYears ) {
+    	// compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextSusceptible = CurrentSusceptible;
+
+    	// Subtract the number of new infections. They aren't infectious yet,
+        // so they move to the exposed group rather than the infected.
+    	nextSusceptible -= RoundedFlow(CurrentSusceptible, InfectionRate);
+
+        // We can't have a negative population
+    	if( nextSusceptible < 0 )
+            nextSusceptible = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentSusceptible = nextSusceptible;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+/* Exposed: This function calculates the number of exposed individuals there
+ *	    will be for the next generation of the simulation. This depends on
+ *	    the number of susceptible individuals who catch the disease, as well
+ *	    as the number of exposed individuals whose incubation period ends.
+ */
+void Exposed() {
+    long nextExposed;
+
+    while( NowYear < NumYears ) {
+        // compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextExposed = CurrentExposed;
+
+    	// Add the new infections and subtract the exposed individuals who
+        // become infectious.
+        nextExposed += RoundedFlow(CurrentSusceptible, InfectionRate);
+        nextExposed -= RoundedFlow(CurrentExposed, IncubationRate);
+
+    	// We still cannot have a negative population
+	if (nextExposed < 0)
+            nextExposed = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentExposed = nextExposed;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+/* Infected: This function calculates the number of infected individuals there
+ *			 will be for the next generation of the simulation. This depends on
+ *			 the number of exposed individuals who become infectious, as well
+ *			 as the number of infected individuals that have recovered.
+ */
+void Infected() {
+    long nextInfected;
+
+    while( NowYear < NumYears ) {
+        // compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextInfected = CurrentInfected;
+
+    	// Add the exposed individuals who became infectious and subtract
+        // the number of recovered individuals.
+        nextInfected += RoundedFlow(CurrentExposed, IncubationRate);
+        nextInfected -= RoundedFlow(CurrentInfected, RecoveryRate);
+
+    	// We still cannot have a negative population
+	if (nextInfected < 0)
+            nextInfected = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentInfected = nextInfected;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+
+/* Recovered: This function calculates the number of individuals who have
+ *			  recovered from their infection. This depends on the number
+ *			  of people who are currently infected.
+ */
+void Recovered() {
+    long nextRecovered = CurrentRecovered;
+
+    while( NowYear < NumYears ) {
+	// Compute a temporary next-value for the number of recovered individuals
+        // based on the current number of infected individuals.
+    	nextRecovered += RoundedFlow(CurrentInfected, RecoveryRate);
+
+    	// DoneComputing barrier: Save the calculated variables to the
+    	// global variables.
+    	#pragma omp barrier
+    	CurrentRecovered = nextRecovered;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+// Watcher: This function adjusts the global variables for the program.
+void Watcher() {
+    int tempMonth;
+    int tempYear;
+
+#ifdef DEBUG
+    // The total population from the previous month, used to check that no one
+    // is created or destroyed by the model.
+    long previousTotal = CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered;
+#endif
+
+    while( NowYear < NumYears ) {
+
+	// DoneComputing barrier:
+	#pragma omp barrier
+
+	// DoneAssigning barrier:
+	#pragma omp barrier
+
+	// Print the current values for the simulation. If the user gave us a CSV
+	// file, the values go there instead of to the terminal.
+        if (CsvFile != NULL) {
+            fprintf(CsvFile, "%d,%ld,%ld,%ld,%ld\n",
+                    12*NowYear + NowMonth, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+        }
+        else {
+#ifdef CSV
+            // Calculate the current month number for graphing purposes.
+            int addMonths = 12*NowYear;
+            int printMonth = NowMonth+addMonths;
+
+            fprintf(stderr, "%2d, %ld, %ld, %ld, %ld\n",
+                    printMonth, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+
+#else
+            fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Exposed: %6ld, Infected: %6ld, Recovered: %6ld\n",
+                    NowYear, NowMonth+1, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+#endif
+        }
+
+#ifdef DEBUG
+        fprintf(stderr, "Total Population: %6ld\n",
+                CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered);
+
+        // Everyone who leaves one group has to enter another, so the total
+        // population must never change.
+        if (CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered != previousTotal)
+            fprintf(stderr, "Warning: The total population changed from %ld to %ld\n",
+                    previousTotal, CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered);
+        previousTotal = CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered;
+#endif
+
+	// Compute a temporary next-value for this quantity
+	// based on the current state of the simulation:
+        tempYear = NowYear;
+	tempMonth = NowMonth + 1;
+
+	if (tempMonth > 11) {
+            tempMonth = 0;
+	    tempYear++;
+        }
+
+        // Store the new environment variables for the simulation.
+        NowMonth = tempMonth;
+	NowYear = tempYear;
+
+	// DonePrinting barrier:
+	#pragma omp barrier
+    }
+}
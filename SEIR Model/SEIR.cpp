@@ -0,0 +1,275 @@
+/*
+ * Program Name: SEIR.cpp
+ * Summary: This program uses multithreading and parallel calculations
+ *          to simulate the progress of a measles outbreak on a human
+ *          population using the SEIR epidemiology model.
+ *              (S)usceptible
+ *              (E)xposed
+ *              (I)nfected
+ *              (R)ecovered
+ *          The newly infected go through an incubation period in the
+ *          exposed group before they can spread the disease.
+ */
+
+// Implementing a matrix-vector multpiplication method to perform the calculations
+// for this epidemiological model.
+
+#include <errno.h>
+#include <limits.h>
+#include <math.h>
+#include <omp.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <time.h>
+
+#include "SEIR.h"
+
+// printUsage: This function prints the usage message for the program, listing
+//             every option flag along with its meaning and default value.
+//  Input:
+//      FILE* stream: The stream to print the message to (stdout when the user
+//                    asked for help, stderr when reporting an error).
+//      char* prog:   The name the program was invoked with (i.e., argv[0]).
+void printUsage(FILE* stream, char* prog) {
+    fprintf(stream, "Usage: %s [-s susceptible] [-e exposed] [-i infected] [-r recovered] [-b rate-of-infection] [-k rate-of-incubation] [-g rate-of-recovery] [-y num-years] [-o csv-file]\n",
+            prog);
+    fprintf(stream, "\n");
+    fprintf(stream, "Options:\n");
+    fprintf(stream, "    -s susceptible        Starting population, including the exposed, infected, and recovered (default: %ld)\n",
+            CurrentSusceptible);
+    fprintf(stream, "    -e exposed            Initial number of exposed individuals (default: %ld)\n",
+            CurrentExposed);
+    fprintf(stream, "    -i infected           Initial number of infected individuals (default: %ld)\n",
+            CurrentInfected);
+    fprintf(stream, "    -r recovered          Initial number of recovered individuals (default: %ld)\n",
+            CurrentRecovered);
+    fprintf(stream, "    -b rate-of-infection  Fraction of the susceptible exposed each month, in [0, 1] (default: %g)\n",
+            InfectionRate);
+    fprintf(stream, "    -k rate-of-incubation Fraction of the exposed becoming infectious each month, in [0, 1] (default: %g)\n",
+            IncubationRate);
+    fprintf(stream, "    -g rate-of-recovery   Fraction of the infected recovering each month, in [0, 1] (default: %g)\n",
+            RecoveryRate);
+    fprintf(stream, "    -y num-years          Number of years to run the simulation (default: %d)\n",
+            NumYears);
+    fprintf(stream, "    -o csv-file           Write the simulation to csv-file instead of the terminal\n");
+    fprintf(stream, "    -h, --help            Print this message and exit\n");
+    fprintf(stream, "\n");
+    fprintf(stream, "Example:\n");
+    fprintf(stream, "    %s -s 100000 -i 10 -b 0.3 -g 0.05 -y 3\n", prog);
+}
+
+// The main loop of the program.
+int main(int argc, char* argv[]) {
+    // Path of the CSV file to write the simulation to. This stays NULL unless
+    // the user provides one with the -o flag.
+    char* csvPath = NULL;
+
+    // Gather the initial values (if any) from the command-line
+    // If command-line arguments are provided, they are parsed in with
+    // the appropriate flags:
+    if (argc >= 2) {
+        // Parse all of the provided arguments and their values.
+        // IMPORTANT: We also need to handle error cases where a user
+        // forgets to enter a value (e.g., ./influenzaOutbreak -s)
+        // This leaves argv[argc-1] as the flag, and the final argv[argc] as NULL.
+
+        // Iterate through the argument vector to locate the flags.
+        int c;
+        char *endptr, *str;
+        double double_val;  // Store values from strtod() (i.e., the rates)
+
+        // Check for the help flags before parsing anything else, so that the
+        // usage message reports the real default values. The help flags also
+        // don't take a value like the other flags do.
+        for (int i = 1; i < argc; ++i) {
+            if (strcmp(argv[i], "-h") == 0 || strcmp(argv[i], "--help") == 0) {
+                printUsage(stdout, argv[0]);
+                exit(EXIT_SUCCESS);
+            }
+        }
+
+        for (int i = 1; i < argc; ++i) {
+            // If the current argument is an option, determine which option
+            // and store the provided value to the corresponding variable
+            if (argv[i][0] == '-') {
+                c = argv[i][1]; // Get the option character
+
+                str = argv[++i];    // Store the pointer that holds the number value
+                                    // to convert.
+
+                // IMPORTANT: If the user didn't actually provide an argument after
+                // one of the argument flags, that will result in str being given a
+                // nullptr. If we try to run strtol() or strtod() on a nullptr, we
+                // get a segfault. 
+                if (str == nullptr) {
+                    fprintf(stderr, "Error: No argument was provided for option flag \"%s\".\n", argv[--i]);
+                    printUsage(stderr, argv[0]);
+                    exit(EXIT_FAILURE);
+ 
+                }
+                
+                switch (c) {
+                    case 's':   // -s: Initial susceptible value. Value must be a
+                                // whole positive integer.
+                        parseLong(&CurrentSusceptible, str);
+                        break;
+                    case 'e':   // -e: Initial exposed value. Value must be a whole
+                                // positive integer.
+                        parseLong(&CurrentExposed, str);
+                        break;
+                    case 'i':   // -i: Initial infected value. Value must be a whole
+                                // positive integer.
+                        parseLong(&CurrentInfected, str);
+                        break;
+                    case 'r':   // -r: Initial recovered value. Value must be a whole
+                                // positive integer.
+                        parseLong(&CurrentRecovered, str);
+                        break;
+                    case 'b':   // -b: Rate of infection (from susceptible to
+                                //      exposed). Must be within [0, 1].
+                        parseRate(&InfectionRate, str);
+                        break;
+                    case 'k':   // -k: Rate of incubation (from exposed to infected).
+                                // Must be within [0, 1].
+                        parseRate(&IncubationRate, str);
+                        break;
+                    case 'g':   // -g: Rate of recovery (from infected to recovered).
+                                // Must be within [0, 1].
+                        parseRate(&RecoveryRate, str);
+                        break;
+		    case 'o':	// -o: File to write the simulation to as CSV data.
+				// The file is only opened once every value has
+				// been checked (see below).
+			csvPath = str;
+			break;
+		    case 'y':	// -y: Number of years to run the simulation.
+				// Must be a non-negative value.
+			parseInt(&NumYears, str);
+			break;
+                    default:    // If the provided argument flag is invalid, print a
+                                // message explaining the flag is invalid. Then print
+                                // the usage message.
+                        fprintf(stderr, "Error: The provided flag of \"%s\" is invalid.\n", argv[--i]);
+                        printUsage(stderr, argv[0]);
+                        exit(EXIT_FAILURE);
+                }
+            }
+            // Any argument that is not an option flag or the value of one is
+            // invalid, so we print an error instead of silently ignoring it.
+            else {
+                fprintf(stderr, "Error: The provided argument \"%s\" is not an option flag.\n", argv[i]);
+                printUsage(stderr, argv[0]);
+                exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    // Make sure that there is a population to simulate at all, and that the
+    // starting population is large enough to hold the initially exposed,
+    // infected, and recovered.
+    if (CurrentSusceptible == 0) {
+        fprintf(stderr, "Error: The starting population must be greater than 0.\n");
+        exit(EXIT_FAILURE);
+    }
+    // The groups are compared one at a time rather than added up, since the
+    // sum of very large values could overflow.
+    if (CurrentExposed > CurrentSusceptible || CurrentInfected > CurrentSusceptible - CurrentExposed ||
+        CurrentRecovered > CurrentSusceptible - CurrentExposed - CurrentInfected) {
+        fprintf(stderr, "Error: The starting population of %ld is smaller than the %ld initially exposed plus the %ld initially infected and the %ld initially recovered.\n",
+                CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+        exit(EXIT_FAILURE);
+    }
+
+    // Once the user-provided values have been parsed (if they were
+    // provided), we need to subtract the number of exposed and infected from
+    // the beginning population of CurrentSusceptible, along with anyone who
+    // starts out already recovered.
+    CurrentSusceptible -= CurrentExposed + CurrentInfected + CurrentRecovered;
+
+    // Report the basic reproduction number, so the rates can be compared with
+    // the standard model. This is left out of the CSV output so that the
+    // output can still be graphed directly.
+#ifndef CSV
+    fprintf(stderr, "Basic Reproduction Number (R0): %.4f\n", BasicReproductionNumber());
+    fprintf(stderr, "Note: R0 assumes new infections of b*S*I/N, but this model's new infections are b*S, so the outbreak does not follow it.\n");
+#endif
+
+    // Open the CSV file only now that all of the values have been checked, so
+    // that a bad value doesn't wipe out an existing file. Any existing file is
+    // overwritten.
+    if (csvPath != NULL) {
+        CsvFile = fopen(csvPath, "w");
+        if (CsvFile == NULL) {
+            perror("fopen");
+            exit(EXIT_FAILURE);
+        }
+    }
+
+    // IMPORTANT: Before we begin our calculations, we need to print out the initial
+    // population values. For a CSV file, that also means writing the header row.
+    if (CsvFile != NULL) {
+        fprintf(CsvFile, "month,susceptible,exposed,infected,recovered\n");
+        fprintf(CsvFile, "%d,%ld,%ld,%ld,%ld\n",
+                12*NowYear + NowMonth, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+    }
+    else {
+#ifdef CSV
+        // Calculate the current month number for graphing purposes.
+        int addMonths = 12*NowYear;
+        int printMonth = NowMonth+addMonths;
+
+        fprintf(stderr, "%2d, %ld, %ld, %ld, %ld\n", printMonth, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+#else
+        fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Exposed: %6ld, Infected: %6ld, Recovered: %6ld\n",
+                NowYear, NowMonth+1, CurrentSusceptible, CurrentExposed, CurrentInfected, CurrentRecovered);
+#endif
+    }
+
+    // For debugging, print the total population as we go.
+#ifdef DEBUG
+    fprintf(stderr, "Total Population: %6ld\n",
+            CurrentSusceptible + CurrentExposed + CurrentInfected + CurrentRecovered);
+#endif
+
+    // Increment to the next month to begin our calculations.
+    NowMonth++;
+
+    omp_set_num_threads(NUMT);	// same as # of sections
+    #pragma omp parallel sections
+    {
+        #pragma omp section
+        {
+	    Susceptible();
+        }
+
+        #pragma omp section
+        {
+            Exposed();
+        }
+
+        #pragma omp section
+        {
+            Infected();
+        }
+		
+        #pragma omp section
+        {
+            Recovered();
+        }
+		
+        #pragma omp section
+        {
+            Watcher();
+        }
+		
+    }   // implied barrier -- all functions must return in order
+	// to allow any of them to get past here
+
+    // Flush and close the CSV file, if there is one, now that the simulation
+    // has finished.
+    if (CsvFile != NULL && fclose(CsvFile) != 0) {
+        perror("fclose");
+        exit(EXIT_FAILURE);
+    }
+}
@@ -0,0 +1,116 @@
+/*
+ * SEIR.h - A header file for the SEIR.cpp program to store
+ *          the declarations and definitions of the most
+ *          important funcations and variables for the program.
+ */
+
+// print debugging messages?
+//#define DEBUG
+
+// setting the number of threads:
+#ifndef NUMT
+#define NUMT    5
+#endif
+
+
+// Define the start and end year for the simulation.
+#ifndef NUM_YEARS
+#define NUM_YEARS  2
+#endif
+
+//#define CSV
+
+// Since the below variables are shared between SEIR.cpp and SEIR.c,
+// we need to declare them as extern variable so that they can be used in all the
+// files that make up this program. The variables are declared here in the header
+// file, are assigned their default values in the SEIR.c file, and can be accessed
+// and changed in the SEIR.cpp file.
+
+
+// Year and month for the simulation to keep track of.
+extern int NowYear;	    // [0, NumYears]
+extern int NumYears;
+extern int NowMonth;	    // [0, 11]
+
+
+// Starting number of susceptible people, exposed people, infected people, and
+// recovered people. The exposed have caught the disease, but can't spread it yet.
+extern long CurrentInfected;
+extern long CurrentSusceptible;
+extern long CurrentExposed;
+extern long CurrentRecovered;
+
+extern double InfectionRate;
+extern double IncubationRate;
+extern double RecoveryRate;
+
+// File to write the simulation to as CSV data, if the user asked for one.
+extern FILE* CsvFile;
+
+
+// parseLong: This function serves as a wrapper for the function strtol(),
+//            ensuring that the provided character string str produces
+//            a long integer that meets our criteria.
+//  Input:
+//      long* long_val: A pointer to the variable where we will be storing our
+//                      converted str.
+//      char* str:      The string that holds the numeric data to convert to a
+//                      long integer
+void parseLong(long* long_val, char* str);
+
+
+// parseInt: This is a wrapper function for the parseLong function that includes
+//	     a check to make sure that the provided value will fit into an integer
+//	     value.
+//  Input:
+//	int* int_val: A pointer to the variable where we will store the converted
+//		      str value.
+//	char* str:    The string that holds the numeric data to convert to an
+//		      integer.
+void parseInt(int* int_val, char* str);
+
+
+// parseDouble: This function serves as a wrapper for the function strtod(),
+//              ensuring that the provided character string str produces
+//              a double that meets our criteria.
+//  Input:
+//      double* double_val: A pointer to the variable where we will be storing our
+//                      converted str.
+//      char* str:      The string that holds the numeric data to convert to a
+//                      double
+void parseDouble(double* double_val, char* str);
+
+
+// parseRate: This is a wrapper function for the parseDouble function that includes
+//	      a check to make sure that the provided value is a valid rate, i.e., a
+//	      fraction within [0, 1].
+//  Input:
+//	double* rate_val: A pointer to the variable where we will store the converted
+//			  str value.
+//	char* str:	  The string that holds the numeric data to convert to a rate.
+void parseRate(double* rate_val, char* str);
+
+
+// BasicReproductionNumber: This function returns the basic reproduction number,
+//                          R0, of the disease, i.e., how many people a single
+//                          infected person infects in a fully susceptible
+//                          population. This is R0 for the standard b*S*I/N
+//                          incidence, which this model's infections don't follow.
+double BasicReproductionNumber();
+
+
+// RoundedFlow: This function returns the number of individuals who leave a group
+//              this month, rounded to the nearest person and never more than the
+//              whole group.
+//  Input:
+//      long source: The number of individuals in the group.
+//      double rate: The fraction of the group leaving it, within [0, 1].
+long RoundedFlow(long source, double rate);
+
+
+// Function prototypes for the functions stored in the SEIR.c file.
+void Susceptible();
+void Exposed();
+void Infected();
+void Recovered();
+void Watcher();
@@ -4,6 +4,7 @@
  */
 
 #include <stdio.h>
+#include <limits.h>
 #include <stdlib.h>
 #include <math.h>
 #include "SIR.h"
@@ -21,25 +22,238 @@ int NowYear = 0;			// [0, Num_Years]
 int NumYears = NUM_YEARS;
 int NowMonth = 0;			// [0, 11]
 
-// Starting number of susceptible people, infected people, and recovered people.
+// Starting number of susceptible people, infected people, recovered people,
+// and vaccinated people.
 long CurrentInfected = 10;
 long CurrentSusceptible = 175000;
 long CurrentRecovered = 0;
+long CurrentVaccinated = 0;
 
 // Transfer rates for the SIR model.
 // Rate of infection for the common cold.
 double InfectionRate = 0.4;
+// Strength of the yearly seasonal swing in the rate of infection, as a
+// fraction of InfectionRate. With the default of 0, the rate of infection
+// stays the same all year round.
+double SeasonalAmplitude = 0.0;
 // Rate of recovery for the common cold.
 double RecoveryRate = 0.04;
+// Rate of vaccination of the susceptible. There is no vaccination campaign
+// unless the user provides a rate with the -v flag.
+double VaccinationRate = 0.0;
+
+// Natural birth and death rates for the population. These default to 0 so
+// that the population stays fixed unless the user provides them with the
+// -n and -d flags.
+double BirthRate = 0.0;
+double DeathRate = 0.0;
+
+// File to write the simulation to as CSV data. This stays NULL unless the
+// user provides one with the -o flag.
+FILE* CsvFile = NULL;
+
+// The peak of the outbreak so far. The Watcher() updates these each month.
+long PeakInfected = 0;
+int PeakMonth = 0;
+
+// Everyone who has ever been infected. The Infected() thread adds the new
+// infections to this each month.
+long CumulativeInfected = 0;
+
+// The number of new infections in the month just computed, i.e., the incidence
+// rather than the prevalence. The Infected() thread sets this each month.
+long CurrentIncidence = 0;
+
+// The month the epidemic turns over, once the Watcher() finds it.
+int TurningPointMonth = -1;
+
+// Early stopping for the simulation. This is turned off unless the user
+// provides a threshold with the -e flag.
+long EndThreshold = -1;
+int EndMonth = -1;
+
+
+/* RoundedFlow: This function returns the number of individuals who leave a group
+ *              of the given size this month at the given rate, rounded to the
+ *              nearest person. The product is rounded as a double, which can
+ *              round a very large group up past LONG_MAX, so the flow is clamped
+ *              to the size of the group before it is converted back to a long.
+ */
+long RoundedFlow(long source, double rate) {
+    double flow = round(source * rate);
+
+    if (flow >= (double)source)
+        return source;
+
+    return flow;
+}
+
+
+/* BasicReproductionNumber: This function returns R0 = InfectionRate / (RecoveryRate
+ *                          + DeathRate). This is the frequency-dependent definition
+ *                          for an SIR model with new infections of InfectionRate *
+ *                          S * I/N, where each infected person infects InfectionRate
+ *                          others a month for the 1 / (RecoveryRate + DeathRate)
+ *                          months they stay infected. Without recovery or death, an
+ *                          infected person never stops spreading the disease, so R0
+ *                          is infinite.
+ * IMPORTANT: This model's new infections are CurrentSusceptible * InfectionRate,
+ *            which doesn't depend on the number of infected at all. The outbreak
+ *            here doesn't follow this R0 (e.g., it still happens with no one
+ *            infected to begin with), so it is only reported to compare the rates
+ *            against the standard model.
+ */
+double BasicReproductionNumber() {
+    if (InfectionRate == 0.0)
+        return 0.0;
+
+    if (RecoveryRate + DeathRate == 0.0)
+        return INFINITY;
+
+    return InfectionRate / (RecoveryRate + DeathRate);
+}
+
+
+/* EffectiveReproductionNumber: This function returns Rt for the coming month,
+ *                              i.e., the number of new infections for every
+ *                              infected individual who recovers or dies. Since
+ *                              this model's new infections don't depend on I,
+ *                              R0 * S/N doesn't mark its turning point. This
+ *                              ratio does: the infected group stops growing
+ *                              exactly when Rt is 1 or less. The counts are
+ *                              rounded the same way the Susceptible() and
+ *                              Infected() threads round them.
+ */
+double EffectiveReproductionNumber() {
+    long newInfections = RoundedFlow(CurrentSusceptible, SeasonalInfectionRate());
+    long newRecoveries = RoundedFlow(CurrentInfected, RecoveryRate);
+    long outflow = newRecoveries + NaturalDeaths(CurrentInfected - newRecoveries);
+
+    // With no new infections, the outbreak can't grow.
+    if (newInfections == 0)
+        return 0.0;
+
+    // With new infections but no one leaving, it can only grow.
+    if (outflow == 0)
+        return INFINITY;
+
+    return (double)newInfections / outflow;
+}
+
+
+/* HerdImmunityThreshold: This function returns the fraction of the population
+ *                        that needs to be immune for the disease to stop
+ *                        spreading, 1 - 1/r0. A disease with an r0 of 1 or less
+ *                        dies out on its own, so no one needs to be immune.
+ */
+double HerdImmunityThreshold(double r0) {
+    if (r0 <= 1.0)
+        return 0.0;
+
+    return 1.0 - 1.0/r0;
+}
+
+
+/* SeasonalInfectionRate: This function returns the rate of infection for the
+ *                        current month, which follows a sine wave over the
+ *                        year: InfectionRate * (1 + SeasonalAmplitude *
+ *                        sin(2*pi*month/12)). This peaks in the fourth month
+ *                        of each year and bottoms out in the tenth. The result
+ *                        is clamped to [0, 1] so it is always a valid rate.
+ */
+double SeasonalInfectionRate() {
+    int month = 12*NowYear + NowMonth;
+    double rate = InfectionRate * (1.0 + SeasonalAmplitude * sin(2.0 * M_PI * month / 12.0));
+
+    if (rate < 0.0)
+        rate = 0.0;
+    if (rate > 1.0)
+        rate = 1.0;
+
+    return rate;
+}
+
+
+/* NewVaccinations: This function returns the number of susceptible individuals
+ *                  who are vaccinated this month. Only the susceptible who
+ *                  weren't just infected can be vaccinated, so the infections
+ *                  and vaccinations never add up to more than the susceptible
+ *                  population. Both the Susceptible() and Vaccinated() threads
+ *                  call this, so they always agree on the number.
+ */
+long NewVaccinations() {
+    long remaining = CurrentSusceptible - RoundedFlow(CurrentSusceptible, SeasonalInfectionRate());
+
+    if (remaining < 0)
+        remaining = 0;
+
+    return RoundedFlow(remaining, VaccinationRate);
+}
+
+
+/* NewBirths: This function returns the number of individuals born this month,
+ *            based on the size of the whole population. Everyone is born
+ *            susceptible. The births are capped so that the whole population
+ *            never grows past LONG_MAX, since every sum of the groups would
+ *            overflow after that.
+ */
+long NewBirths() {
+    long population = CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated;
+    double births = round(population * BirthRate);
+
+    // Compare before converting back to a long, which could overflow itself.
+    if (births >= (double)(LONG_MAX - population))
+        return LONG_MAX - population;
+
+    return births;
+}
+
+
+/* NaturalDeaths: This function returns the number of natural deaths this month
+ *                out of the given number of survivors. Each group passes in the
+ *                individuals left over after its other outflows (e.g., the new
+ *                infections and vaccinations), so that no one can both leave a
+ *                group and die in it during the same month.
+ */
+long NaturalDeaths(long survivors) {
+    return RoundedFlow(survivors, DeathRate);
+}
+
+
+/* PrintSummary: This function prints the summary statistics for the whole
+ *               simulation: the height and timing of the peak of the outbreak,
+ *               when the outbreak turned over, its final size, and how much of
+ *               the population would need to be vaccinated to stop the spread
+ *               of the disease.
+ */
+void PrintSummary(long initialPopulation) {
+    fprintf(stderr, "Peak Infection: %ld infected in Year %d, Month %d\n",
+            PeakInfected, PeakMonth / 12, PeakMonth % 12 + 1);
+
+    if (TurningPointMonth >= 0)
+        fprintf(stderr, "Turning Point: Rt fell to 1 or below in Year %d, Month %d\n",
+                TurningPointMonth / 12, TurningPointMonth % 12 + 1);
+    else
+        fprintf(stderr, "Turning Point: Rt never fell to 1 or below\n");
+
+    fprintf(stderr, "Final Epidemic Size: %ld ever infected (Attack Rate: %.4f)\n",
+            CumulativeInfected,
+            initialPopulation > 0 ? (double)CumulativeInfected / initialPopulation : 0.0);
+
+    fprintf(stderr, "Vaccination Needed for Herd Immunity: %.4f of the population (under b*S*I/N incidence)\n",
+            HerdImmunityThreshold(BasicReproductionNumber()));
+}
 
 
 /* Susceptible: This function is executed by a thread in parallel with the
- *              Infected(), Recovered(), and Watcher() functions. It serves
- *              to calculate the next value of the Susceptible population,
- *              as the Susceptible become Infected.
+ *              Infected(), Recovered(), Vaccinated(), and Watcher() functions.
+ *              It serves to calculate the next value of the Susceptible
+ *              population, as the Susceptible become Infected or Vaccinated.
  */
 void Susceptible() {
     long nextSusceptible;
+    long newInfections;
+    long newVaccinations;
 
     while( NowYear < NumYears ) {
     	// compute a temporary next-value for this quantity
@@ -51,7 +265,17 @@ void Susceptible() {
         // IMPORTANT: While it is mathematically correct to find the change in
         //            the Susceptible population by multiplying
         //            CurrentSusceptible *
-    	nextSusceptible -= round(CurrentSusceptible * InfectionRate);
+        newInfections = RoundedFlow(CurrentSusceptible, SeasonalInfectionRate());
+    	nextSusceptible -= newInfections;
+
+        // Subtract the number of susceptible individuals who were vaccinated.
+        newVaccinations = NewVaccinations();
+        nextSusceptible -= newVaccinations;
+
+        // Add the newborns and subtract the natural deaths among those who
+        // were neither infected nor vaccinated.
+        nextSusceptible += NewBirths();
+        nextSusceptible -= NaturalDeaths(CurrentSusceptible - newInfections - newVaccinations);
 	
         // We can't have a negative population
     	if( nextSusceptible < 0 )
@@ -76,6 +300,9 @@ void Susceptible() {
  */
 void Infected() {
     long nextInfected;
+    long newInfections;
+    long newRecoveries;
+    long nextCumulativeInfected;
 	
     while( NowYear < NumYears ) {
         // compute a temporary next-value for this quantity
@@ -84,16 +311,31 @@ void Infected() {
 
     	// Add the new number of infected indivuduals and subtract
         // the number of recovered individuals.
-        nextInfected += round(CurrentSusceptible * InfectionRate);
-        nextInfected -= round(CurrentInfected * RecoveryRate);
+        newInfections = RoundedFlow(CurrentSusceptible, SeasonalInfectionRate());
+        newRecoveries = RoundedFlow(CurrentInfected, RecoveryRate);
+        nextInfected += newInfections;
+        nextInfected -= newRecoveries;
+
+        // Subtract the natural deaths among those who didn't recover.
+        nextInfected -= NaturalDeaths(CurrentInfected - newRecoveries);
 
     	// We still cannot have a negative population
 	if (nextInfected < 0)
             nextInfected = 0;
 
+        // Keep a running total of everyone who has been infected. With births
+        // the population keeps being replenished, so over a long enough run
+        // the total could overflow, and we saturate at LONG_MAX instead.
+        if (newInfections > LONG_MAX - CumulativeInfected)
+            nextCumulativeInfected = LONG_MAX;
+        else
+            nextCumulativeInfected = CumulativeInfected + newInfections;
+
     	// DoneComputing barrier:
     	#pragma omp barrier
     	CurrentInfected = nextInfected;
+        CumulativeInfected = nextCumulativeInfected;
+        CurrentIncidence = newInfections;
 
     	// DoneAssigning barrier:
     	#pragma omp barrier
@@ -110,13 +352,18 @@ void Infected() {
  */
 void Recovered() {
     long nextRecovered = CurrentRecovered;
+    long newRecoveries;
 
     while( NowYear < NumYears ) {
 	// Compute a temporary next-value for the number of recovered individuals
         // based on the current number of infected individuals.
+        newRecoveries = RoundedFlow(CurrentInfected, RecoveryRate);
+
+        // Subtract the natural deaths.
+        nextRecovered -= NaturalDeaths(CurrentRecovered);
+
+    	nextRecovered += newRecoveries;
 
-    	nextRecovered += round(CurrentInfected * RecoveryRate);
-		
     	// DoneComputing barrier: Save the calculated variables to the
     	// global variables.
     	#pragma omp barrier
@@ -130,36 +377,115 @@ void Recovered() {
     }
 }
 
+/* Vaccinated: This function calculates the number of individuals who have
+ *	       been vaccinated. The vaccinated move straight from the susceptible
+ *	       group without ever being infected, and can't be infected again.
+ */
+void Vaccinated() {
+    long nextVaccinated = CurrentVaccinated;
+
+    while( NowYear < NumYears ) {
+	// Compute a temporary next-value for the number of vaccinated individuals
+	// based on the current number of susceptible individuals.
+	nextVaccinated += NewVaccinations();
+
+	// Subtract the natural deaths.
+	nextVaccinated -= NaturalDeaths(CurrentVaccinated);
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentVaccinated = nextVaccinated;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
 // Watcher: This function adjusts the global variables for the program.
 void Watcher() {
     int tempMonth;
     int tempYear;
 
+#ifdef DEBUG
+    // The total population we expect after this month, used to check that no
+    // one is created or destroyed by the model.
+    long expectedTotal;
+    long newInfections;
+    long newRecoveries;
+#endif
+
+    // The values from the previous month, used to find out when the outbreak
+    // has stopped changing.
+    long previousSusceptible = CurrentSusceptible;
+    long previousInfected = CurrentInfected;
+    long previousRecovered = CurrentRecovered;
+    long previousVaccinated = CurrentVaccinated;
+
     while( NowYear < NumYears ) {
 
+        // Record the first month where the epidemic turns over. Rt depends on
+        // the month the other threads are about to compute, so it has to be
+        // found before the DoneComputing barrier. It belongs to the month that
+        // was printed last.
+        if (TurningPointMonth < 0 && EffectiveReproductionNumber() <= 1.0)
+            TurningPointMonth = 12*NowYear + NowMonth - 1;
+
+#ifdef DEBUG
+        // Everyone who leaves one group has to enter another, so the total
+        // population can only change by the births and natural deaths.
+        newInfections = RoundedFlow(CurrentSusceptible, SeasonalInfectionRate());
+        newRecoveries = RoundedFlow(CurrentInfected, RecoveryRate);
+        expectedTotal = CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated + NewBirths()
+                        - NaturalDeaths(CurrentSusceptible - newInfections - NewVaccinations())
+                        - NaturalDeaths(CurrentInfected - newRecoveries)
+                        - NaturalDeaths(CurrentRecovered)
+                        - NaturalDeaths(CurrentVaccinated);
+#endif
+
 	// DoneComputing barrier:
 	#pragma omp barrier
 
 	// DoneAssigning barrier:
 	#pragma omp barrier
 
-	// Print the current values for the simulation.
+	// Print the current values for the simulation. If the user gave us a CSV
+	// file, the values go there instead of to the terminal.
+        if (CsvFile != NULL) {
+            fprintf(CsvFile, "%d,%ld,%ld,%ld,%ld,%ld,%ld\n",
+                    12*NowYear + NowMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
+        }
+        else {
 #ifdef CSV
-        // Calculate the current month number for graphing purposes.
-        int addMonths = 12*NowYear;
-        int printMonth = NowMonth+addMonths;
+            // Calculate the current month number for graphing purposes.
+            int addMonths = 12*NowYear;
+            int printMonth = NowMonth+addMonths;
 
-        fprintf(stderr, "%2d, %ld, %ld, %ld\n",
-                printMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+            fprintf(stderr, "%2d, %ld, %ld, %ld, %ld, %ld, %ld\n",
+                    printMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
 
 #else
-        fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld, Recovered: %6ld\n",
-                NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+            fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld, Recovered: %6ld, Vaccinated: %6ld, New Infections: %6ld, Ever Infected: %6ld\n",
+                    NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
 #endif
+        }
+
+        // Keep track of the peak of the outbreak. Only a strictly larger value
+        // replaces the peak, so we report the earliest month when there's a tie.
+        if (CurrentInfected > PeakInfected) {
+            PeakInfected = CurrentInfected;
+            PeakMonth = 12*NowYear + NowMonth;
+        }
 
 #ifdef DEBUG
-        fprintf(stderr, "Total Population: %6d\n",
-                CurrentSusceptible + CurrentInfected + CurrentRecovered);
+        fprintf(stderr, "Total Population: %6ld\n",
+                CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated);
+
+        if (CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated != expectedTotal)
+            fprintf(stderr, "Warning: The total population should be %ld, but it is %ld\n",
+                    expectedTotal, CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated);
 #endif
 
 	// Compute a temporary next-value for this quantity
@@ -172,6 +498,24 @@ void Watcher() {
 	    tempYear++;
         }
 
+        // If no group changed by more than the EndThreshold, the outbreak has
+        // settled, so we end the simulation here rather than printing the same
+        // values again. The main() makes sure this is never combined with
+        // seasonal forcing.
+        if (EndThreshold >= 0 &&
+            labs(CurrentSusceptible - previousSusceptible) <= EndThreshold &&
+            labs(CurrentInfected - previousInfected) <= EndThreshold &&
+            labs(CurrentRecovered - previousRecovered) <= EndThreshold &&
+            labs(CurrentVaccinated - previousVaccinated) <= EndThreshold) {
+            EndMonth = 12*NowYear + NowMonth;
+            tempYear = NumYears;
+        }
+
+        previousSusceptible = CurrentSusceptible;
+        previousInfected = CurrentInfected;
+        previousRecovered = CurrentRecovered;
+        previousVaccinated = CurrentVaccinated;
+
         // Store the new environment variables for the simulation.
         NowMonth = tempMonth;
 	NowYear = tempYear;
@@ -19,12 +19,66 @@
 #include <omp.h>
 #include <stdio.h>
 #include <stdlib.h>
+#include <string.h>
 #include <time.h>
 
 #include "SIR.h"
 
+// printUsage: This function prints the usage message for the program, listing
+//             every option flag along with its meaning and default value.
+//  Input:
+//      FILE* stream: The stream to print the message to (stdout when the user
+//                    asked for help, stderr when reporting an error).
+//      char* prog:   The name the program was invoked with (i.e., argv[0]).
+void printUsage(FILE* stream, char* prog) {
+    fprintf(stream, "Usage: %s [-s susceptible] [-i infected] [-r recovered] [-V vaccinated] [-b rate-of-infection] [-a seasonal-amplitude] [-g rate-of-recovery] [-v rate-of-vaccination] [-n birth-rate] [-d death-rate] [-y num-years] [-t elapsed-months] [-e end-threshold] [-o csv-file]\n",
+            prog);
+    fprintf(stream, "\n");
+    fprintf(stream, "Options:\n");
+    fprintf(stream, "    -s susceptible        Starting population, including the infected, recovered, and vaccinated (default: %ld)\n",
+            CurrentSusceptible);
+    fprintf(stream, "    -i infected           Initial number of infected individuals (default: %ld)\n",
+            CurrentInfected);
+    fprintf(stream, "    -r recovered          Initial number of recovered individuals (default: %ld)\n",
+            CurrentRecovered);
+    fprintf(stream, "    -V vaccinated         Initial number of vaccinated individuals (default: %ld)\n",
+            CurrentVaccinated);
+    fprintf(stream, "    -b rate-of-infection  Fraction of the susceptible infected each month, in [0, 1] (default: %g)\n",
+            InfectionRate);
+    fprintf(stream, "    -a seasonal-amplitude Yearly swing in the rate of infection, as a fraction of it, in [0, 1] (default: %g)\n",
+            SeasonalAmplitude);
+    fprintf(stream, "    -g rate-of-recovery   Fraction of the infected recovering each month, in [0, 1] (default: %g)\n",
+            RecoveryRate);
+    fprintf(stream, "    -v rate-of-vaccination  Fraction of the susceptible vaccinated each month, in [0, 1] (default: %g)\n",
+            VaccinationRate);
+    fprintf(stream, "    -n birth-rate         Births each month as a fraction of the population, in [0, 1] (default: %g)\n",
+            BirthRate);
+    fprintf(stream, "    -d death-rate         Fraction of every group dying of natural causes each month, in [0, 1] (default: %g)\n",
+            DeathRate);
+    fprintf(stream, "    -y num-years          Number of years to run the simulation (default: %d)\n",
+            NumYears);
+    fprintf(stream, "    -t elapsed-months     Months already simulated, to resume a run from a saved state (default: off)\n");
+    fprintf(stream, "                          The summary statistics are left out of a resumed run, and its\n");
+    fprintf(stream, "                          ever-infected count starts from the resumed infected\n");
+    fprintf(stream, "    -e end-threshold      Stop early once no group changes by more than end-threshold people in a month (default: off)\n");
+    fprintf(stream, "                          Can't be combined with a nonzero -a\n");
+    fprintf(stream, "    -o csv-file           Write the simulation to csv-file instead of the terminal\n");
+    fprintf(stream, "    -h, --help            Print this message and exit\n");
+    fprintf(stream, "\n");
+    fprintf(stream, "Example:\n");
+    fprintf(stream, "    %s -s 100000 -i 10 -b 0.3 -g 0.05 -y 3\n", prog);
+}
+
 // The main loop of the program.
 int main(int argc, char* argv[]) {
+    // Path of the CSV file to write the simulation to. This stays NULL unless
+    // the user provides one with the -o flag.
+    char* csvPath = NULL;
+
+    // Months already simulated when resuming a run. This stays -1 unless the
+    // user provides them with the -t flag.
+    int elapsedMonths = -1;
+
     // Gather the initial values (if any) from the command-line
     // If command-line arguments are provided, they are parsed in with
     // the appropriate flags:
@@ -39,6 +93,16 @@ int main(int argc, char* argv[]) {
         char *endptr, *str;
         double double_val;  // Store values from strtod() (i.e., the rates)
 
+        // Check for the help flags before parsing anything else, so that the
+        // usage message reports the real default values. The help flags also
+        // don't take a value like the other flags do.
+        for (int i = 1; i < argc; ++i) {
+            if (strcmp(argv[i], "-h") == 0 || strcmp(argv[i], "--help") == 0) {
+                printUsage(stdout, argv[0]);
+                exit(EXIT_SUCCESS);
+            }
+        }
+
         for (int i = 1; i < argc; ++i) {
             // If the current argument is an option, determine which option
             // and store the provided value to the corresponding variable
@@ -54,8 +118,7 @@ int main(int argc, char* argv[]) {
                 // get a segfault. 
                 if (str == nullptr) {
                     fprintf(stderr, "Error: No argument was provided for option flag \"%s\".\n", argv[--i]);
-                    fprintf(stderr, "Usage: %s [-s susceptible] [-i infected] [-b rate-of-infection] [-g rate-of-recovery] [-y num-years]\n",
-                        argv[0]);
+                    printUsage(stderr, argv[0]);
                     exit(EXIT_FAILURE);
  
                 }
@@ -69,14 +132,53 @@ int main(int argc, char* argv[]) {
                                 // positive integer.
                         parseLong(&CurrentInfected, str);
                         break;
+                    case 'r':   // -r: Initial recovered value. Value must be a whole
+                                // positive integer.
+                        parseLong(&CurrentRecovered, str);
+                        break;
+                    case 'V':   // -V: Initial vaccinated value. Value must be a whole
+                                // positive integer.
+                        parseLong(&CurrentVaccinated, str);
+                        break;
                     case 'b':   // -b: Rate of infection (from susceptible to
-                                //      infected). Must be a non-negative value.
-                        parseDouble(&InfectionRate, str);
+                                //      infected). Must be within [0, 1].
+                        parseRate(&InfectionRate, str);
+                        break;
+                    case 'a':   // -a: Amplitude of the seasonal swing in the rate of
+                                // infection. Must be within [0, 1].
+                        parseRate(&SeasonalAmplitude, str);
                         break;
                     case 'g':   // -g: Rate of recovery (from infected to recovered).
-                                // Must be a non-negative value.
-                        parseDouble(&RecoveryRate, str);
+                                // Must be within [0, 1].
+                        parseRate(&RecoveryRate, str);
+                        break;
+                    case 'v':   // -v: Rate of vaccination (from susceptible to
+                                // vaccinated). Must be within [0, 1].
+                        parseRate(&VaccinationRate, str);
+                        break;
+                    case 'n':   // -n: Birth rate (newborns enter the susceptible
+                                // group). Must be within [0, 1].
+                        parseRate(&BirthRate, str);
                         break;
+                    case 'd':   // -d: Natural death rate (removed from every
+                                // group). Must be within [0, 1].
+                        parseRate(&DeathRate, str);
+                        break;
+		    case 't':	// -t: Number of months that were already simulated
+				// before the provided starting values. Must be a
+				// non-negative value.
+			parseInt(&elapsedMonths, str);
+			break;
+		    case 'e':	// -e: Stop the simulation early once no group changes
+				// by more than this many people in a month. Must be
+				// a non-negative value.
+			parseLong(&EndThreshold, str);
+			break;
+		    case 'o':	// -o: File to write the simulation to as CSV data.
+				// The file is only opened once every value has
+				// been checked (see below).
+			csvPath = str;
+			break;
 		    case 'y':	// -y: Number of years to run the simulation.
 				// Must be a non-negative value.
 			parseInt(&NumYears, str);
@@ -85,40 +187,129 @@ int main(int argc, char* argv[]) {
                                 // message explaining the flag is invalid. Then print
                                 // the usage message.
                         fprintf(stderr, "Error: The provided flag of \"%s\" is invalid.\n", argv[--i]);
-                        fprintf(stderr, "Usage: %s [-s susceptible] [-i infected] [-b rate-of-infection] [-g rate-of-recovery] [-y num-years]\n",
-                                 argv[0]);
+                        printUsage(stderr, argv[0]);
                         exit(EXIT_FAILURE);
                 }
             }
+            // Any argument that is not an option flag or the value of one is
+            // invalid, so we print an error instead of silently ignoring it.
+            else {
+                fprintf(stderr, "Error: The provided argument \"%s\" is not an option flag.\n", argv[i]);
+                printUsage(stderr, argv[0]);
+                exit(EXIT_FAILURE);
+            }
+        }
+
+        // When resuming a run, pick up the dates where the saved run left off
+        // so that the labels continue from there instead of starting over.
+        // IMPORTANT: Dividing instead of multiplying NumYears by 12 keeps a
+        // large number of years from overflowing.
+        if (elapsedMonths >= 0) {
+            if (elapsedMonths / 12 >= NumYears) {
+                fprintf(stderr, "Error: The %d elapsed months already cover all %d years of the simulation.\n",
+                        elapsedMonths, NumYears);
+                exit(EXIT_FAILURE);
+            }
+            NowYear = elapsedMonths / 12;
+            NowMonth = elapsedMonths % 12;
         }
     }
 
+    // With seasonal forcing the rate of infection changes every month, so a
+    // quiet month doesn't mean the outbreak is over, and an early stop could
+    // cut off the next season's outbreak.
+    if (EndThreshold >= 0 && SeasonalAmplitude != 0.0) {
+        fprintf(stderr, "Error: The end threshold (-e) can't be combined with a seasonal amplitude (-a).\n");
+        exit(EXIT_FAILURE);
+    }
+
+    // Make sure that there is a population to simulate at all, and that the
+    // starting population is large enough to hold the initially infected,
+    // recovered, and vaccinated.
+    if (CurrentSusceptible == 0) {
+        fprintf(stderr, "Error: The starting population must be greater than 0.\n");
+        exit(EXIT_FAILURE);
+    }
+    // The groups are compared one at a time rather than added up, since the
+    // sum of very large values could overflow.
+    if (CurrentInfected > CurrentSusceptible || CurrentRecovered > CurrentSusceptible - CurrentInfected ||
+        CurrentVaccinated > CurrentSusceptible - CurrentInfected - CurrentRecovered) {
+        fprintf(stderr, "Error: The starting population of %ld is smaller than the %ld initially infected plus the %ld initially recovered and the %ld initially vaccinated.\n",
+                CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated);
+        exit(EXIT_FAILURE);
+    }
+
     // Once the user-provided values have been parsed (if they were
     // provided), we need to subtract the number of infected from the
-    // beginning population of CurrentSusceptible
-    CurrentSusceptible -= CurrentInfected;
+    // beginning population of CurrentSusceptible, along with anyone who
+    // starts out already recovered or vaccinated.
+    CurrentSusceptible -= CurrentInfected + CurrentRecovered + CurrentVaccinated;
+
+    // Report the basic reproduction number, so the rates can be compared with
+    // the standard model. This is left out of the CSV output so that the
+    // output can still be graphed directly.
+#ifndef CSV
+    fprintf(stderr, "Basic Reproduction Number (R0): %.4f\n", BasicReproductionNumber());
+    fprintf(stderr, "Note: R0 assumes new infections of b*S*I/N, but this model's new infections are b*S, so the outbreak does not follow it.\n");
+#endif
+
+    // The initial population is where the peak of the outbreak starts out, and
+    // the initially infected are the first to count towards its final size.
+    // This has to happen before the first month is printed, since the running
+    // total of infections is printed along with it.
+    PeakInfected = CurrentInfected;
+    PeakMonth = 12*NowYear + NowMonth;
+    CumulativeInfected = CurrentInfected;
+
+    // Open the CSV file only now that all of the values have been checked, so
+    // that a bad value doesn't wipe out an existing file. Any existing file is
+    // overwritten.
+    if (csvPath != NULL) {
+        CsvFile = fopen(csvPath, "w");
+        if (CsvFile == NULL) {
+            perror("fopen");
+            exit(EXIT_FAILURE);
+        }
+    }
 
     // IMPORTANT: Before we begin our calculations, we need to print out the initial
-    // population values.
+    // population values. For a CSV file, that also means writing the header row.
+    if (CsvFile != NULL) {
+        fprintf(CsvFile, "month,susceptible,infected,recovered,vaccinated,new_infections,cumulative_infected\n");
+        fprintf(CsvFile, "%d,%ld,%ld,%ld,%ld,%ld,%ld\n",
+                12*NowYear + NowMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
+    }
+    else {
 #ifdef CSV
-    // Calculate the current month number for graphing purposes.
-    int addMonths = 12*NowYear;
-    int printMonth = NowMonth+addMonths;
+        // Calculate the current month number for graphing purposes.
+        int addMonths = 12*NowYear;
+        int printMonth = NowMonth+addMonths;
 
-    fprintf(stderr, "%2d, %ld, %ld, %ld\n", printMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+        fprintf(stderr, "%2d, %ld, %ld, %ld, %ld, %ld, %ld\n", printMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
 #else
-    fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld, Recovered: %6ld\n",
-            NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+        fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld, Recovered: %6ld, Vaccinated: %6ld, New Infections: %6ld, Ever Infected: %6ld\n",
+                NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected, CurrentRecovered, CurrentVaccinated, CurrentIncidence, CumulativeInfected);
 #endif
+    }
 
     // For debugging, print the total population as we go.
 #ifdef DEBUG
     fprintf(stderr, "Total Population: %6ld\n",
-            CurrentSusceptible + CurrentInfected + CurrentRecovered);
+            CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated);
 #endif
 
-    // Increment to the next month to begin our calculations.
+    // Save the starting population so we can find what fraction of it was
+    // infected by the end of the simulation.
+    long initialPopulation = CurrentSusceptible + CurrentInfected + CurrentRecovered + CurrentVaccinated;
+
+    // Increment to the next month to begin our calculations. A resumed run
+    // might start in the last month of a year, so roll over to the next year
+    // just like the Watcher() does.
     NowMonth++;
+    if (NowMonth > 11) {
+        NowMonth = 0;
+        NowYear++;
+    }
 
     omp_set_num_threads(NUMT);	// same as # of sections
     #pragma omp parallel sections
@@ -138,6 +329,11 @@ int main(int argc, char* argv[]) {
             Recovered();
         }
 		
+        #pragma omp section
+        {
+            Vaccinated();
+        }
+
         #pragma omp section
         {
             Watcher();
@@ -145,4 +341,29 @@ int main(int argc, char* argv[]) {
 		
     }   // implied barrier -- all functions must return in order
 	// to allow any of them to get past here
+
+    // Report the summary statistics for the whole simulation. A resumed run
+    // never saw the months before it, so it can't know the real peak, turning
+    // point, or final size of the outbreak, and we leave them out instead of
+    // reporting misleading values.
+#ifndef CSV
+    // Whether the run ended early only depends on the months simulated here,
+    // so this is reported for a resumed run too.
+    if (EndMonth >= 0)
+        fprintf(stderr, "Simulation Ended Early: The outbreak settled in Year %d, Month %d\n",
+                EndMonth / 12, EndMonth % 12 + 1);
+
+    if (elapsedMonths < 0)
+        PrintSummary(initialPopulation);
+    else
+        fprintf(stderr, "Summary: Left out of a resumed run, since the months before Year %d, Month %d weren't simulated here.\n",
+                elapsedMonths / 12, elapsedMonths % 12 + 1);
+#endif
+
+    // Flush and close the CSV file, if there is one, now that the simulation
+    // has finished.
+    if (CsvFile != NULL && fclose(CsvFile) != 0) {
+        perror("fclose");
+        exit(EXIT_FAILURE);
+    }
 }
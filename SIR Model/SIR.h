@@ -9,7 +9,7 @@
 
 // setting the number of threads:
 #ifndef NUMT
-#define NUMT    4
+#define NUMT    5
 #endif
 
 
@@ -33,13 +33,47 @@ extern int NumYears;
 extern int NowMonth;	    // [0, 11]
 
 
-// Starting number of susceptible people, infected people, and recovered people.
+// Starting number of susceptible people, infected people, recovered people,
+// and vaccinated people.
 extern long CurrentInfected;
 extern long CurrentSusceptible;
 extern long CurrentRecovered;
+extern long CurrentVaccinated;
 
 extern double InfectionRate;
+extern double SeasonalAmplitude;
 extern double RecoveryRate;
+extern double VaccinationRate;
+
+// Vital dynamics for the population, i.e., the natural birth and death rates.
+extern double BirthRate;
+extern double DeathRate;
+
+// File to write the simulation to as CSV data, if the user asked for one.
+extern FILE* CsvFile;
+
+// The largest number of infected individuals seen so far, and the month it
+// happened in (counting from the start of the simulation).
+extern long PeakInfected;
+extern int PeakMonth;
+
+// Total number of individuals who have ever been infected, including the
+// initially infected.
+extern long CumulativeInfected;
+
+// Number of new infections in the current month of the simulation (0 for the
+// starting month).
+extern long CurrentIncidence;
+
+// The first month (counting from the start of the simulation) in which the
+// effective reproduction number fell to 1 or below, or -1 if it hasn't yet.
+extern int TurningPointMonth;
+
+// The simulation stops early once no group changes by more than EndThreshold
+// people in a month (-1 to always run for NumYears). EndMonth is the month it
+// stopped in, or -1 if it ran to the end.
+extern long EndThreshold;
+extern int EndMonth;
 
 
 // parseLong: This function serves as a wrapper for the function strtol(),
@@ -75,8 +109,84 @@ void parseInt(int* int_val, char* str);
 void parseDouble(double* double_val, char* str);
 
 
+// parseRate: This is a wrapper function for the parseDouble function that includes
+//	      a check to make sure that the provided value is a valid rate, i.e., a
+//	      fraction within [0, 1].
+//  Input:
+//	double* rate_val: A pointer to the variable where we will store the converted
+//			  str value.
+//	char* str:	  The string that holds the numeric data to convert to a rate.
+void parseRate(double* rate_val, char* str);
+
+
+// BasicReproductionNumber: This function returns the basic reproduction number,
+//                          R0, of the disease, i.e., how many people a single
+//                          infected person infects in a fully susceptible
+//                          population. This is R0 for the standard b*S*I/N
+//                          incidence, which this model's infections don't follow.
+double BasicReproductionNumber();
+
+
+// EffectiveReproductionNumber: This function returns the effective reproduction
+//                              number, Rt, for the current state of the
+//                              simulation, i.e., the new infections in the coming
+//                              month for every infected individual who recovers
+//                              or dies.
+double EffectiveReproductionNumber();
+
+
+// HerdImmunityThreshold: This function returns the fraction of the population
+//                        that needs to be immune (e.g., through vaccination) for
+//                        the disease to stop spreading.
+//  Input:
+//      double r0: The basic reproduction number of the disease.
+double HerdImmunityThreshold(double r0);
+
+
+// SeasonalInfectionRate: This function returns the rate of infection for the
+//                        current month of the simulation, after applying the
+//                        yearly seasonal forcing to the base InfectionRate.
+double SeasonalInfectionRate();
+
+
+// NewVaccinations: This function returns the number of susceptible individuals
+//                  who are vaccinated this month.
+long NewVaccinations();
+
+
+// NewBirths: This function returns the number of individuals born this month,
+//            all of whom enter the susceptible group.
+long NewBirths();
+
+
+// NaturalDeaths: This function returns the number of natural deaths this month
+//                out of the given number of survivors.
+//  Input:
+//      long survivors: The individuals in a group who are left over after its
+//                      other outflows for this month.
+long NaturalDeaths(long survivors);
+
+
+// PrintSummary: This function prints the summary statistics for the whole
+//               simulation once it has finished.
+//  Input:
+//      long initialPopulation: The size of the population when the simulation
+//                              started.
+void PrintSummary(long initialPopulation);
+
+
+// RoundedFlow: This function returns the number of individuals who leave a group
+//              this month, rounded to the nearest person and never more than the
+//              whole group.
+//  Input:
+//      long source: The number of individuals in the group.
+//      double rate: The fraction of the group leaving it, within [0, 1].
+long RoundedFlow(long source, double rate);
+
+
 // Function prototypes for the functions stored in the SIR.c file.
 void Susceptible();
 void Infected();
 void Recovered();
+void Vaccinated();
 void Watcher();
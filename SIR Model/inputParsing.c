@@ -118,3 +118,28 @@ void parseDouble(double* double_val, char* str) {
     // provided converted double into the provided double pointer.
 }
 
+
+// parseRate: This is a wrapper function for the parseDouble function that includes
+//	      a check to make sure that the provided value is a valid rate, i.e., a
+//	      fraction within [0, 1].
+//  Input:
+//	double* rate_val: A pointer to the variable where we will store the converted
+//			  str value.
+//	char* str:	  The string that holds the numeric data to convert to a rate.
+void parseRate(double* rate_val, char* str) {
+
+    double temp = 0.0;	// A double to store the value that is converted from str.
+
+    parseDouble(&temp, str);
+
+    // Now check that the value falls within [0, 1]. Writing the check this way
+    // also rejects NaN, since every comparison with NaN is false.
+    if (!(temp >= 0.0 && temp <= 1.0)) {
+	fprintf(stderr, "parseRate: The provided rate of %f must be between 0 and 1\n", temp);
+	exit(EXIT_FAILURE);
+    }
+
+    // If the temp value is a valid rate, we can store it in the provided double.
+    *rate_val = temp;
+
+}
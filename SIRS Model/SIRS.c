@@ -0,0 +1,231 @@
+/*
+ * SIRS.c - This function stores all of the differential functions that are
+ *          used with the functional multiprocessing method to produce our SIRS model.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <math.h>
+#include "SIRS.h"
+
+// These variables are initialized and assigned their values in the SIRS.cpp
+// program, but we still need them to be within the scope of the SIRS.c file.
+// Thus, they are declared here, but they are not initialized.
+
+// Year and month for the simulation to keep track of.
+int NowYear = 0;			// [0, Num_Years]
+int NumYears = NUM_YEARS;
+int NowMonth = 0;			// [0, 11]
+
+// Starting number of susceptible people, infected people, and recovered people.
+long CurrentInfected = 10;
+long CurrentSusceptible = 175000;
+long CurrentRecovered = 0;
+
+// Transfer rates for the SIRS model.
+// Rate of infection for seasonal influenza.
+double InfectionRate = 0.4;
+// Rate of recovery for seasonal influenza.
+double RecoveryRate = 0.04;
+// Rate at which the recovered lose their immunity and become susceptible again.
+double ImmunityLossRate = 0.08;
+
+// File to write the simulation to as CSV data. This stays NULL unless the
+// user provides one with the -o flag.
+FILE* CsvFile = NULL;
+
+
+/* RoundedFlow: This function returns the number of individuals who leave a group
+ *              of the given size this month at the given rate, rounded to the
+ *              nearest person. The product is rounded as a double, which can
+ *              round a very large group up past LONG_MAX, so the flow is clamped
+ *              to the size of the group before it is converted back to a long.
+ */
+long RoundedFlow(long source, double rate) {
+    double flow = round(source * rate);
+
+    if (flow >= (double)source)
+        return source;
+
+    return flow;
+}
+
+
+/* Susceptible: This function is executed by a thread in parallel with the
+ *              Infected(), Recovered(), and Watcher() functions. It serves
+ *              to calculate the next value of the Susceptible population,
+ *              as the Susceptible become Infected and the Recovered lose
+ *              their immunity.
+ */
+void Susceptible() {
+    long nextSusceptible;
+
+    while( NowYear < NumYears ) {
+    	// compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextSusceptible = CurrentSusceptible;
+
+    	// Subtract the number of new infections based on the current number
+        // of infected individuals.
+        // IMPORTANT: While it is mathematically correct to find the change in
+        //            the Susceptible population by multiplying
+        //            CurrentSusceptible *
+    	nextSusceptible -= RoundedFlow(CurrentSusceptible, InfectionRate);
+
+        // Add back the recovered individuals whose immunity has worn off.
+        nextSusceptible += RoundedFlow(CurrentRecovered, ImmunityLossRate);
+	
+        // We can't have a negative population
+    	if( nextSusceptible < 0 )
+            nextSusceptible = 0;
+		
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentSusceptible = nextSusceptible;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier		
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier		
+    }
+}
+
+/* Infected: This function calculates the number of infected individuals there
+ *			 will be for the next generation of the simulation. This depends on
+ *			 the number of susceptible individuals available to be infected, as
+ *			 well as the number of infected individuals that have recovered.
+ */
+void Infected() {
+    long nextInfected;
+	
+    while( NowYear < NumYears ) {
+        // compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextInfected = CurrentInfected;
+
+    	// Add the new number of infected indivuduals and subtract
+        // the number of recovered individuals.
+        nextInfected += RoundedFlow(CurrentSusceptible, InfectionRate);
+        nextInfected -= RoundedFlow(CurrentInfected, RecoveryRate);
+
+    	// We still cannot have a negative population
+	if (nextInfected < 0)
+            nextInfected = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentInfected = nextInfected;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+
+/* Recovered: This function calculates the number of individuals who have
+ *			  recovered from their flu infection. This depends on the number
+ *			  of people who are currently infected, as well as the number
+ *			  of recovered individuals whose immunity has worn off.
+ */
+void Recovered() {
+    long nextRecovered;
+    long newRecoveries;
+
+    while( NowYear < NumYears ) {
+	// Compute a temporary next-value for the number of recovered individuals
+        // based on the current number of infected individuals.
+        nextRecovered = CurrentRecovered;
+        newRecoveries = RoundedFlow(CurrentInfected, RecoveryRate);
+
+        // Subtract the recovered individuals who lose their immunity.
+        nextRecovered -= RoundedFlow(CurrentRecovered, ImmunityLossRate);
+
+    	nextRecovered += newRecoveries;
+
+    	// DoneComputing barrier: Save the calculated variables to the
+    	// global variables.
+    	#pragma omp barrier
+    	CurrentRecovered = nextRecovered;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier		
+    }
+}
+
+// Watcher: This function adjusts the global variables for the program.
+void Watcher() {
+    int tempMonth;
+    int tempYear;
+
+#ifdef DEBUG
+    // The total population from the previous month, used to check that no one
+    // is created or destroyed by the model.
+    long previousTotal = CurrentSusceptible + CurrentInfected + CurrentRecovered;
+#endif
+
+    while( NowYear < NumYears ) {
+
+	// DoneComputing barrier:
+	#pragma omp barrier
+
+	// DoneAssigning barrier:
+	#pragma omp barrier
+
+	// Print the current values for the simulation. If the user gave us a CSV
+	// file, the values go there instead of to the terminal.
+        if (CsvFile != NULL) {
+            fprintf(CsvFile, "%d,%ld,%ld,%ld\n",
+                    12*NowYear + NowMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+        }
+        else {
+#ifdef CSV
+            // Calculate the current month number for graphing purposes.
+            int addMonths = 12*NowYear;
+            int printMonth = NowMonth+addMonths;
+
+            fprintf(stderr, "%2d, %ld, %ld, %ld\n",
+                    printMonth, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+
+#else
+            fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld, Recovered: %6ld\n",
+                    NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected, CurrentRecovered);
+#endif
+        }
+
+#ifdef DEBUG
+        fprintf(stderr, "Total Population: %6ld\n",
+                CurrentSusceptible + CurrentInfected + CurrentRecovered);
+
+        // Everyone who leaves one group has to enter another, so the total
+        // population must never change.
+        if (CurrentSusceptible + CurrentInfected + CurrentRecovered != previousTotal)
+            fprintf(stderr, "Warning: The total population changed from %ld to %ld\n",
+                    previousTotal, CurrentSusceptible + CurrentInfected + CurrentRecovered);
+        previousTotal = CurrentSusceptible + CurrentInfected + CurrentRecovered;
+#endif
+
+	// Compute a temporary next-value for this quantity
+	// based on the current state of the simulation:
+        tempYear = NowYear;
+	tempMonth = NowMonth + 1;
+
+	if (tempMonth > 11) {
+            tempMonth = 0;
+	    tempYear++;
+        }
+
+        // Store the new environment variables for the simulation.
+        NowMonth = tempMonth;
+	NowYear = tempYear;
+
+	// DonePrinting barrier:
+	#pragma omp barrier
+    }
+}
@@ -0,0 +1,204 @@
+/*
+ * SIS.c - This function stores all of the differential functions that are
+ *         used with the functional multiprocessing method to produce our SIS model.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <math.h>
+#include "SIS.h"
+
+// These variables are initialized and assigned their values in the SIS.cpp
+// program, but we still need them to be within the scope of the SIS.c file.
+// Thus, they are declared here, but they are not initialized.
+
+// Year and month for the simulation to keep track of.
+int NowYear = 0;			// [0, Num_Years]
+int NumYears = NUM_YEARS;
+int NowMonth = 0;			// [0, 11]
+
+// Starting number of susceptible people and infected people.
+long CurrentInfected = 10;
+long CurrentSusceptible = 175000;
+
+// Transfer rates for the SIS model.
+// Rate of infection for gonorrhea.
+double InfectionRate = 0.1;
+// Rate of recovery for gonorrhea. Recovery gives no immunity, so the
+// recovered return straight to the susceptible group.
+double RecoveryRate = 0.3;
+
+// File to write the simulation to as CSV data. This stays NULL unless the
+// user provides one with the -o flag.
+FILE* CsvFile = NULL;
+
+
+/* RoundedFlow: This function returns the number of individuals who leave a group
+ *              of the given size this month at the given rate, rounded to the
+ *              nearest person. The product is rounded as a double, which can
+ *              round a very large group up past LONG_MAX, so the flow is clamped
+ *              to the size of the group before it is converted back to a long.
+ */
+long RoundedFlow(long source, double rate) {
+    double flow = round(source * rate);
+
+    if (flow >= (double)source)
+        return source;
+
+    return flow;
+}
+
+
+/* EndemicEquilibrium: This function returns the infected fraction of the
+ *                     population at the model's steady state. Since the
+ *                     S -> I flow is CurrentSusceptible * InfectionRate, the
+ *                     population settles where S * InfectionRate balances
+ *                     I * RecoveryRate.
+ */
+double EndemicEquilibrium() {
+    // Without any new infections, the disease eventually dies out.
+    if (InfectionRate == 0.0)
+        return 0.0;
+
+    return InfectionRate / (InfectionRate + RecoveryRate);
+}
+
+
+/* Susceptible: This function is executed by a thread in parallel with the
+ *              Infected() and Watcher() functions. It serves to calculate
+ *              the next value of the Susceptible population, as the
+ *              Susceptible become Infected and the Infected recover.
+ */
+void Susceptible() {
+    long nextSusceptible;
+
+    while( NowYear < NumYears ) {
+    	// compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextSusceptible = CurrentSusceptible;
+
+    	// Subtract the number of new infections, and add back everyone who
+        // recovered, since they have no immunity to the disease.
+    	nextSusceptible -= RoundedFlow(CurrentSusceptible, InfectionRate);
+        nextSusceptible += RoundedFlow(CurrentInfected, RecoveryRate);
+
+        // We can't have a negative population
+    	if( nextSusceptible < 0 )
+            nextSusceptible = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentSusceptible = nextSusceptible;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+/* Infected: This function calculates the number of infected individuals there
+ *			 will be for the next generation of the simulation. This depends on
+ *			 the number of susceptible individuals available to be infected, as
+ *			 well as the number of infected individuals that have recovered.
+ */
+void Infected() {
+    long nextInfected;
+
+    while( NowYear < NumYears ) {
+        // compute a temporary next-value for this quantity
+    	// based on the current state of the simulation:
+    	nextInfected = CurrentInfected;
+
+    	// Add the new number of infected indivuduals and subtract
+        // the number of recovered individuals.
+        nextInfected += RoundedFlow(CurrentSusceptible, InfectionRate);
+        nextInfected -= RoundedFlow(CurrentInfected, RecoveryRate);
+
+    	// We still cannot have a negative population
+	if (nextInfected < 0)
+            nextInfected = 0;
+
+    	// DoneComputing barrier:
+    	#pragma omp barrier
+    	CurrentInfected = nextInfected;
+
+    	// DoneAssigning barrier:
+    	#pragma omp barrier
+
+    	// DonePrinting barrier:
+    	#pragma omp barrier
+    }
+}
+
+// Watcher: This function adjusts the global variables for the program.
+void Watcher() {
+    int tempMonth;
+    int tempYear;
+
+#ifdef DEBUG
+    // The total population from the previous month, used to check that no one
+    // is created or destroyed by the model.
+    long previousTotal = CurrentSusceptible + CurrentInfected;
+#endif
+
+    while( NowYear < NumYears ) {
+
+	// DoneComputing barrier:
+	#pragma omp barrier
+
+	// DoneAssigning barrier:
+	#pragma omp barrier
+
+	// Print the current values for the simulation. If the user gave us a CSV
+	// file, the values go there instead of to the terminal.
+        if (CsvFile != NULL) {
+            fprintf(CsvFile, "%d,%ld,%ld\n",
+                    12*NowYear + NowMonth, CurrentSusceptible, CurrentInfected);
+        }
+        else {
+#ifdef CSV
+            // Calculate the current month number for graphing purposes.
+            int addMonths = 12*NowYear;
+            int printMonth = NowMonth+addMonths;
+
+            fprintf(stderr, "%2d, %ld, %ld\n",
+                    printMonth, CurrentSusceptible, CurrentInfected);
+
+#else
+            fprintf(stderr, "Year %4d, Month %2d - Susceptible: %6ld, Infected: %6ld\n",
+                    NowYear, NowMonth+1, CurrentSusceptible, CurrentInfected);
+#endif
+        }
+
+#ifdef DEBUG
+        fprintf(stderr, "Total Population: %6ld\n",
+                CurrentSusceptible + CurrentInfected);
+
+        // Everyone who leaves one group has to enter another, so the total
+        // population must never change.
+        if (CurrentSusceptible + CurrentInfected != previousTotal)
+            fprintf(stderr, "Warning: The total population changed from %ld to %ld\n",
+                    previousTotal, CurrentSusceptible + CurrentInfected);
+        previousTotal = CurrentSusceptible + CurrentInfected;
+#endif
+
+	// Compute a temporary next-value for this quantity
+	// based on the current state of the simulation:
+        tempYear = NowYear;
+	tempMonth = NowMonth + 1;
+
+	if (tempMonth > 11) {
+            tempMonth = 0;
+	    tempYear++;
+        }
+
+        // Store the new environment variables for the simulation.
+        NowMonth = tempMonth;
+	NowYear = tempYear;
+
+	// DonePrinting barrier:
+	#pragma omp barrier
+    }
+}